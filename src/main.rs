@@ -6,132 +6,400 @@ use std::fs::File;
 use std::io::prelude::*;
 use std::path::PathBuf;
 
+#[derive(Deserialize, Debug)]
+struct ImportRow {
+    #[serde(rename = "type")]
+    transaction_type: String,
+    client: String,
+    tx: u32,
+    amount: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum BatchOperation {
+    Deposit {
+        account: String,
+        amount: String,
+        description: String,
+    },
+    Withdrawal {
+        account: String,
+        amount: String,
+        description: String,
+    },
+    Transfer {
+        source: String,
+        destination: String,
+        amount: String,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+struct DepositBody {
+    amount: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct WithdrawBody {
+    amount: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+struct TransferBody {
+    source: String,
+    destination: String,
+    amount: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Account {
     name: String,
-    balance: f64,
+    // Balances are stored as fixed-point minor units (see `MONEY_SCALE`) so
+    // repeated deposits/withdrawals never accumulate floating-point error.
+    available: i64,
+    held: i64,
+    total: i64,
+    locked: bool,
     transactions: Vec<Transaction>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 struct Transaction {
+    tx: u32,
     date: String,
     description: String,
-    amount: f64,
+    amount: i64,
     transaction_type: TransactionType,
+    disputed: bool,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+/// Minor units per whole currency unit. Four decimal places covers the
+/// precision the CSV import format implies while staying exact integer math.
+const MONEY_SCALE: i64 = 10_000;
+
+fn parse_money(input: &str) -> Result<i64, FinnError> {
+    let trimmed = input.trim();
+    let negative = trimmed.starts_with('-');
+    let unsigned = trimmed.trim_start_matches(['+', '-']);
+
+    let mut parts = unsigned.splitn(2, '.');
+    let whole = parts.next().unwrap_or("");
+    let fraction = parts.next().unwrap_or("");
+    if fraction.len() > 4 || (whole.is_empty() && fraction.is_empty()) {
+        return Err(FinnError::InvalidAmount(input.to_string()));
+    }
+
+    let whole_value: i64 = if whole.is_empty() {
+        0
+    } else {
+        whole
+            .parse()
+            .map_err(|_| FinnError::InvalidAmount(input.to_string()))?
+    };
+    let mut fraction_digits = fraction.to_string();
+    while fraction_digits.len() < 4 {
+        fraction_digits.push('0');
+    }
+    let fraction_value: i64 = fraction_digits
+        .parse()
+        .map_err(|_| FinnError::InvalidAmount(input.to_string()))?;
+
+    let magnitude = whole_value
+        .checked_mul(MONEY_SCALE)
+        .and_then(|scaled| scaled.checked_add(fraction_value))
+        .ok_or_else(|| FinnError::InvalidAmount(input.to_string()))?;
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+fn parse_money_arg(input: &str) -> Result<i64, String> {
+    parse_money(input).map_err(|err| err.to_string())
+}
+
+fn format_money(value: i64) -> String {
+    let negative = value < 0;
+    let magnitude = value.unsigned_abs();
+    let whole = magnitude / MONEY_SCALE as u64;
+    let fraction = magnitude % MONEY_SCALE as u64;
+    format!("{}{}.{:04}", if negative { "-" } else { "" }, whole, fraction)
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 enum TransactionType {
     Deposit,
     Withdrawal,
     Transfer,
 }
 
+enum FinnError {
+    Io(std::io::Error),
+    Serde(serde_json::Error),
+    AccountNotFound(String),
+    InsufficientFunds,
+    AccountLocked,
+    BatchFailed(usize, Box<FinnError>),
+    InvalidAmount(String),
+}
+
+impl std::fmt::Display for FinnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FinnError::Io(err) => write!(f, "I/O error: {}", err),
+            FinnError::Serde(err) => write!(f, "failed to (de)serialize accounts: {}", err),
+            FinnError::AccountNotFound(name) => write!(f, "account `{}` not found", name),
+            FinnError::InsufficientFunds => write!(f, "insufficient funds"),
+            FinnError::AccountLocked => write!(f, "account is locked"),
+            FinnError::BatchFailed(index, err) => {
+                write!(f, "batch operation #{} failed: {}", index, err)
+            }
+            FinnError::InvalidAmount(raw) => write!(f, "invalid amount `{}`", raw),
+        }
+    }
+}
+
+// Delegate Debug to Display so `fn main() -> Result<(), FinnError>` prints a
+// readable message instead of a raw enum dump when a command fails.
+impl std::fmt::Debug for FinnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::error::Error for FinnError {}
+
+impl From<std::io::Error> for FinnError {
+    fn from(err: std::io::Error) -> Self {
+        FinnError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for FinnError {
+    fn from(err: serde_json::Error) -> Self {
+        FinnError::Serde(err)
+    }
+}
+
 impl Account {
-    fn new(name: String, balance: f64) -> Self {
+    fn new(name: String) -> Self {
         Account {
             name,
-            balance,
+            available: 0,
+            held: 0,
+            total: 0,
+            locked: false,
             transactions: Vec::new(),
         }
     }
 
-    fn deposit(&mut self, transaction: Transaction) {
-        self.balance += transaction.amount;
+    fn deposit(&mut self, transaction: Transaction) -> Result<(), FinnError> {
+        if self.locked {
+            return Err(FinnError::AccountLocked);
+        }
+        self.available += transaction.amount;
+        self.total += transaction.amount;
         self.transactions.push(transaction);
+        Ok(())
     }
 
-    fn withdraw(&mut self, transaction: Transaction) {
-        if self.balance >= transaction.amount {
-            self.balance -= transaction.amount;
+    fn withdraw(&mut self, transaction: Transaction) -> Result<(), FinnError> {
+        if self.locked {
+            return Err(FinnError::AccountLocked);
+        }
+        if self.available >= transaction.amount {
+            self.available -= transaction.amount;
+            self.total -= transaction.amount;
             self.transactions.push(transaction);
+            Ok(())
         } else {
-            println!("Insufficient funds.")
+            Err(FinnError::InsufficientFunds)
         }
     }
 
-    fn transfer_to(&mut self, other_account: &mut Account, amount: f64) {
-        if self.balance > -amount {
-            self.balance -= amount;
-            other_account.balance += amount;
+    fn transfer_to(
+        &mut self,
+        other_account: &mut Account,
+        amount: i64,
+        next_tx: &mut u32,
+    ) -> Result<(), FinnError> {
+        if self.locked || other_account.locked {
+            return Err(FinnError::AccountLocked);
+        }
+        if self.available >= amount {
+            self.available -= amount;
+            self.total -= amount;
+            other_account.available += amount;
+            other_account.total += amount;
             self.transactions.push(Transaction {
+                tx: *next_tx,
                 amount,
                 description: format!("Transfer to {}", other_account.name),
                 date: Utc::now().format("%Y-%m-%d").to_string(),
                 transaction_type: TransactionType::Transfer,
+                disputed: false,
             });
+            *next_tx += 1;
             other_account.transactions.push(Transaction {
+                tx: *next_tx,
                 amount,
                 description: format!("Transfer from {}", self.name),
                 date: Utc::now().format("%Y-%m-%d").to_string(),
                 transaction_type: TransactionType::Transfer,
-            })
+                disputed: false,
+            });
+            *next_tx += 1;
+            Ok(())
         } else {
-            println!("Insufficient funds.");
+            Err(FinnError::InsufficientFunds)
         }
     }
 
+    fn dispute(&mut self, tx: u32) -> Result<(), FinnError> {
+        if self.locked {
+            return Err(FinnError::AccountLocked);
+        }
+        // Only deposits can be disputed: a withdrawal or transfer-out leg
+        // already debited `amount` from `available`, so moving it to `held`
+        // a second time would make `available` go spuriously negative.
+        if let Some(transaction) = self.transactions.iter_mut().find(|t| {
+            t.tx == tx && !t.disputed && t.transaction_type == TransactionType::Deposit
+        }) {
+            transaction.disputed = true;
+            self.available -= transaction.amount;
+            self.held += transaction.amount;
+        }
+        Ok(())
+    }
+
+    fn resolve(&mut self, tx: u32) -> Result<(), FinnError> {
+        if self.locked {
+            return Err(FinnError::AccountLocked);
+        }
+        if let Some(transaction) = self
+            .transactions
+            .iter_mut()
+            .find(|t| t.tx == tx && t.disputed)
+        {
+            transaction.disputed = false;
+            self.held -= transaction.amount;
+            self.available += transaction.amount;
+        }
+        Ok(())
+    }
+
+    fn chargeback(&mut self, tx: u32) -> Result<(), FinnError> {
+        if self.locked {
+            return Err(FinnError::AccountLocked);
+        }
+        if let Some(transaction) = self
+            .transactions
+            .iter_mut()
+            .find(|t| t.tx == tx && t.disputed)
+        {
+            transaction.disputed = false;
+            self.held -= transaction.amount;
+            self.total -= transaction.amount;
+            self.locked = true;
+        }
+        Ok(())
+    }
+
     fn display_history(&self) {
         println!("Transaction history for {}", self.name);
         for transaction in &self.transactions {
             println!(
-                "{} - ${:.2} - {}",
-                transaction.date, transaction.amount, transaction.description,
+                "#{} {} - ${} - {}{}",
+                transaction.tx,
+                transaction.date,
+                format_money(transaction.amount),
+                transaction.description,
+                if transaction.disputed { " (disputed)" } else { "" },
             );
         }
     }
 }
 
-fn add_account(accounts: &mut Vec<Account>, name: String, balance: f64, description: String) {
-    let mut account = Account::new(name, balance);
+fn add_account(
+    accounts: &mut Vec<Account>,
+    name: String,
+    balance: i64,
+    description: String,
+    next_tx: &mut u32,
+) -> Result<(), FinnError> {
+    let mut account = Account::new(name);
     println!("new account {}", account.name);
     let transaction = Transaction {
+        tx: *next_tx,
         amount: balance,
         description,
         date: Utc::now().format("%Y-%m-%d").to_string(),
         transaction_type: TransactionType::Deposit,
+        disputed: false,
     };
-    account.transactions.push(transaction);
+    *next_tx += 1;
+    account.deposit(transaction)?;
     accounts.push(account);
+    Ok(())
 }
 
-fn deposit_funds(accounts: &mut Vec<Account>, name: String, amount: f64, description: String) {
-    if let Some(account) = accounts.iter_mut().find(|a| a.name == name) {
-        let transaction = Transaction {
-            amount,
-            description,
-            date: Utc::now().format("%Y-%m-%d").to_string(),
-            transaction_type: TransactionType::Deposit,
-        };
-        account.deposit(transaction);
-        println!("successful");
-    } else {
-        println!("account not found");
-    }
+fn deposit_funds(
+    accounts: &mut Vec<Account>,
+    name: String,
+    amount: i64,
+    description: String,
+    next_tx: &mut u32,
+) -> Result<(), FinnError> {
+    let account = accounts
+        .iter_mut()
+        .find(|a| a.name == name)
+        .ok_or_else(|| FinnError::AccountNotFound(name.clone()))?;
+    let transaction = Transaction {
+        tx: *next_tx,
+        amount,
+        description,
+        date: Utc::now().format("%Y-%m-%d").to_string(),
+        transaction_type: TransactionType::Deposit,
+        disputed: false,
+    };
+    *next_tx += 1;
+    account.deposit(transaction)?;
+    println!("successful");
+    Ok(())
 }
 
-fn withdraw_funds(accounts: &mut Vec<Account>, name: String, amount: f64, description: String) {
-    if let Some(account) = accounts.iter_mut().find(|a| a.name == name) {
-        let transaction = Transaction {
-            amount,
-            description,
-            date: Utc::now().format("%Y-%m-%d").to_string(),
-            transaction_type: TransactionType::Withdrawal,
-        };
-        account.withdraw(transaction);
-        println!("successful");
-    } else {
-        println!("account not found");
-    }
+fn withdraw_funds(
+    accounts: &mut Vec<Account>,
+    name: String,
+    amount: i64,
+    description: String,
+    next_tx: &mut u32,
+) -> Result<(), FinnError> {
+    let account = accounts
+        .iter_mut()
+        .find(|a| a.name == name)
+        .ok_or_else(|| FinnError::AccountNotFound(name.clone()))?;
+    let transaction = Transaction {
+        tx: *next_tx,
+        amount,
+        description,
+        date: Utc::now().format("%Y-%m-%d").to_string(),
+        transaction_type: TransactionType::Withdrawal,
+        disputed: false,
+    };
+    *next_tx += 1;
+    account.withdraw(transaction)?;
+    println!("successful");
+    Ok(())
 }
 
 fn transfer_funds(
     accounts: &mut Vec<Account>,
     source_name: String,
     destination_name: String,
-    amount: f64,
-) {
+    amount: i64,
+    next_tx: &mut u32,
+) -> Result<(), FinnError> {
     let mut source_account = None;
     let mut destination_account = None;
 
@@ -147,12 +415,332 @@ fn transfer_funds(
         }
     }
 
-    if let (Some(source), Some(destination)) = (source_account, destination_account) {
-        source.transfer_to(destination, amount);
-        println!("successful");
-    } else {
-        println!("account(s) not found");
+    let source =
+        source_account.ok_or_else(|| FinnError::AccountNotFound(source_name.clone()))?;
+    let destination = destination_account
+        .ok_or_else(|| FinnError::AccountNotFound(destination_name.clone()))?;
+    source.transfer_to(destination, amount, next_tx)?;
+    println!("successful");
+    Ok(())
+}
+
+// Rows are applied directly against `Account::deposit`/`withdraw`/`dispute`/
+// `resolve`/`chargeback` rather than through `deposit_funds`/`withdraw_funds`/
+// `transfer_funds`: those helpers always mint a fresh tx id from `next_tx`,
+// but imported deposit/withdrawal tx ids must come from the CSV itself so
+// that dispute/resolve/chargeback rows later in the same file can reference
+// them by id. The `type,client,tx,amount` schema has no destination column,
+// so a `transfer` row can't be expressed and is rejected with an explicit
+// error rather than silently skipped.
+fn import_transactions(
+    accounts: &mut Vec<Account>,
+    path: String,
+    next_tx: &mut u32,
+) -> Result<(), FinnError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(&path)
+        .map_err(|err| {
+            FinnError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("failed to open `{}`: {}", path, err),
+            ))
+        })?;
+
+    for result in reader.deserialize::<ImportRow>() {
+        let row = match result {
+            Ok(row) => row,
+            Err(err) => {
+                println!("skipping malformed row: {}", err);
+                continue;
+            }
+        };
+
+        if !accounts.iter().any(|a| a.name == row.client) {
+            accounts.push(Account::new(row.client.clone()));
+        }
+        let account = accounts
+            .iter_mut()
+            .find(|a| a.name == row.client)
+            .expect("account was just inserted");
+
+        // Imported tx ids come from the CSV itself so later dispute/resolve/
+        // chargeback rows in the same file can reference them by id.
+        let description = format!("import tx {}", row.tx);
+        let amount = match row.amount.as_deref().map(parse_money) {
+            Some(Ok(amount)) => amount,
+            Some(Err(err)) => {
+                println!("row tx {} failed: {}", row.tx, err);
+                continue;
+            }
+            None => 0,
+        };
+        let result = match row.transaction_type.to_lowercase().as_str() {
+            "deposit" => account.deposit(Transaction {
+                tx: row.tx,
+                amount,
+                description,
+                date: Utc::now().format("%Y-%m-%d").to_string(),
+                transaction_type: TransactionType::Deposit,
+                disputed: false,
+            }),
+            "withdrawal" | "withdraw" => account.withdraw(Transaction {
+                tx: row.tx,
+                amount,
+                description,
+                date: Utc::now().format("%Y-%m-%d").to_string(),
+                transaction_type: TransactionType::Withdrawal,
+                disputed: false,
+            }),
+            "dispute" => account.dispute(row.tx),
+            "resolve" => account.resolve(row.tx),
+            "chargeback" => account.chargeback(row.tx),
+            "transfer" => {
+                println!(
+                    "row tx {} failed: `transfer` rows are not supported by import \
+                     (the `type,client,tx,amount` schema has no destination column), skipping",
+                    row.tx
+                );
+                continue;
+            }
+            other => {
+                println!("unknown transaction type `{}`, skipping", other);
+                Ok(())
+            }
+        };
+        if let Err(err) = result {
+            println!("row tx {} failed: {}", row.tx, err);
+        }
+
+        if row.tx >= *next_tx {
+            *next_tx = row.tx + 1;
+        }
+    }
+
+    Ok(())
+}
+
+fn run_batch(
+    accounts: &Vec<Account>,
+    path: String,
+    next_tx: &mut u32,
+) -> Result<Vec<Account>, FinnError> {
+    let contents = std::fs::read_to_string(&path)?;
+    let operations: Vec<BatchOperation> = serde_json::from_str(&contents)?;
+
+    let mut working = accounts.clone();
+    let mut working_next_tx = *next_tx;
+
+    for (index, operation) in operations.into_iter().enumerate() {
+        let result = (|| -> Result<(), FinnError> {
+            match operation {
+                BatchOperation::Deposit {
+                    account,
+                    amount,
+                    description,
+                } => {
+                    let amount = parse_money(&amount)?;
+                    deposit_funds(&mut working, account, amount, description, &mut working_next_tx)
+                }
+                BatchOperation::Withdrawal {
+                    account,
+                    amount,
+                    description,
+                } => {
+                    let amount = parse_money(&amount)?;
+                    withdraw_funds(&mut working, account, amount, description, &mut working_next_tx)
+                }
+                BatchOperation::Transfer {
+                    source,
+                    destination,
+                    amount,
+                } => {
+                    let amount = parse_money(&amount)?;
+                    transfer_funds(&mut working, source, destination, amount, &mut working_next_tx)
+                }
+            }
+        })();
+
+        if let Err(err) = result {
+            return Err(FinnError::BatchFailed(index, Box::new(err)));
+        }
     }
+
+    *next_tx = working_next_tx;
+    Ok(working)
+}
+
+fn status_for(err: &FinnError) -> u16 {
+    match err {
+        FinnError::AccountNotFound(_) => 404,
+        FinnError::InsufficientFunds | FinnError::AccountLocked => 409,
+        FinnError::InvalidAmount(_) => 400,
+        FinnError::Io(_) | FinnError::Serde(_) | FinnError::BatchFailed(..) => 500,
+    }
+}
+
+fn json_response(status: u16, body: &serde_json::Value) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    let data = serde_json::to_vec(body).unwrap_or_default();
+    let content_type =
+        tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..])
+            .expect("static header is valid");
+    tiny_http::Response::from_data(data)
+        .with_status_code(status)
+        .with_header(content_type)
+}
+
+fn read_json_body<T: serde::de::DeserializeOwned>(
+    request: &mut tiny_http::Request,
+) -> Result<T, String> {
+    let mut body = String::new();
+    request
+        .as_reader()
+        .read_to_string(&mut body)
+        .map_err(|err| err.to_string())?;
+    serde_json::from_str(&body).map_err(|err| err.to_string())
+}
+
+fn handle_request(
+    request: &mut tiny_http::Request,
+    accounts: &mut Vec<Account>,
+    next_tx: &mut u32,
+) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    let method = request.method().clone();
+    let url = request.url().to_string();
+    let segments: Vec<&str> = url
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    match (&method, segments.as_slice()) {
+        (tiny_http::Method::Get, ["accounts"]) => json_response(200, &serde_json::json!(accounts)),
+        (tiny_http::Method::Get, ["accounts", name, "history"]) => {
+            match accounts.iter().find(|a| &a.name == name) {
+                Some(account) => json_response(200, &serde_json::json!(account.transactions)),
+                None => json_response(
+                    404,
+                    &serde_json::json!({ "error": format!("account `{}` not found", name) }),
+                ),
+            }
+        }
+        (tiny_http::Method::Post, ["accounts", name, "deposit"]) => {
+            let body: DepositBody = match read_json_body(request) {
+                Ok(body) => body,
+                Err(message) => return json_response(400, &serde_json::json!({ "error": message })),
+            };
+            let outcome = parse_money(&body.amount).and_then(|amount| {
+                deposit_funds(
+                    accounts,
+                    name.to_string(),
+                    amount,
+                    body.description.unwrap_or_else(|| "HTTP deposit".to_string()),
+                    next_tx,
+                )
+            })
+            .and_then(|_| save_accounts(accounts));
+            match outcome {
+                Ok(()) => json_response(200, &serde_json::json!({ "status": "ok" })),
+                Err(err) => json_response(
+                    status_for(&err),
+                    &serde_json::json!({ "error": err.to_string() }),
+                ),
+            }
+        }
+        (tiny_http::Method::Post, ["accounts", name, "withdraw"]) => {
+            let body: WithdrawBody = match read_json_body(request) {
+                Ok(body) => body,
+                Err(message) => return json_response(400, &serde_json::json!({ "error": message })),
+            };
+            let outcome = parse_money(&body.amount).and_then(|amount| {
+                withdraw_funds(
+                    accounts,
+                    name.to_string(),
+                    amount,
+                    body.description.unwrap_or_else(|| "HTTP withdrawal".to_string()),
+                    next_tx,
+                )
+            })
+            .and_then(|_| save_accounts(accounts));
+            match outcome {
+                Ok(()) => json_response(200, &serde_json::json!({ "status": "ok" })),
+                Err(err) => json_response(
+                    status_for(&err),
+                    &serde_json::json!({ "error": err.to_string() }),
+                ),
+            }
+        }
+        (tiny_http::Method::Post, ["transfer"]) => {
+            let body: TransferBody = match read_json_body(request) {
+                Ok(body) => body,
+                Err(message) => return json_response(400, &serde_json::json!({ "error": message })),
+            };
+            let outcome = parse_money(&body.amount)
+                .and_then(|amount| {
+                    transfer_funds(accounts, body.source, body.destination, amount, next_tx)
+                })
+                .and_then(|_| save_accounts(accounts));
+            match outcome {
+                Ok(()) => json_response(200, &serde_json::json!({ "status": "ok" })),
+                Err(err) => json_response(
+                    status_for(&err),
+                    &serde_json::json!({ "error": err.to_string() }),
+                ),
+            }
+        }
+        _ => json_response(404, &serde_json::json!({ "error": "not found" })),
+    }
+}
+
+fn serve(accounts: Vec<Account>, next_tx: u32, port: u16, address: &str) -> Result<(), FinnError> {
+    let server = tiny_http::Server::http(format!("{}:{}", address, port)).map_err(|err| {
+        FinnError::Io(std::io::Error::new(std::io::ErrorKind::Other, err.to_string()))
+    })?;
+    println!("Finn listening on http://{}:{}", address, port);
+
+    let state = std::sync::Arc::new(std::sync::Mutex::new((accounts, next_tx)));
+
+    for mut request in server.incoming_requests() {
+        let state = std::sync::Arc::clone(&state);
+        std::thread::spawn(move || {
+            let mut guard = state.lock().unwrap();
+            let (accounts, next_tx) = &mut *guard;
+            let response = handle_request(&mut request, accounts, next_tx);
+            if let Err(err) = request.respond(response) {
+                println!("failed to send response: {}", err);
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn dispute_transaction(accounts: &mut Vec<Account>, name: String, tx: u32) -> Result<(), FinnError> {
+    let account = accounts
+        .iter_mut()
+        .find(|a| a.name == name)
+        .ok_or_else(|| FinnError::AccountNotFound(name.clone()))?;
+    account.dispute(tx)
+}
+
+fn resolve_transaction(accounts: &mut Vec<Account>, name: String, tx: u32) -> Result<(), FinnError> {
+    let account = accounts
+        .iter_mut()
+        .find(|a| a.name == name)
+        .ok_or_else(|| FinnError::AccountNotFound(name.clone()))?;
+    account.resolve(tx)
+}
+
+fn chargeback_transaction(
+    accounts: &mut Vec<Account>,
+    name: String,
+    tx: u32,
+) -> Result<(), FinnError> {
+    let account = accounts
+        .iter_mut()
+        .find(|a| a.name == name)
+        .ok_or_else(|| FinnError::AccountNotFound(name.clone()))?;
+    account.chargeback(tx)
 }
 
 fn display_transaction_history(accounts: &Vec<Account>, name: String) {
@@ -163,32 +751,145 @@ fn display_transaction_history(accounts: &Vec<Account>, name: String) {
     }
 }
 
-fn save_accounts(accounts: &Vec<Account>) {
-    let mut bin_dir = PathBuf::from(env::var("HOME").unwrap());
+fn home_accounts_path() -> Result<PathBuf, FinnError> {
+    let home = env::var("HOME").map_err(|_| {
+        FinnError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "$HOME is not set",
+        ))
+    })?;
+    let mut bin_dir = PathBuf::from(home);
     bin_dir.push("bin");
     bin_dir.push("accounts.json");
+    Ok(bin_dir)
+}
 
-    let serialized = serde_json::to_string(&accounts).expect("Failed to serialize accounts.");
-    let mut file = File::create(bin_dir).expect("Failed to create file.");
-    file.write_all(serialized.as_bytes())
-        .expect("Failed to write to file.");
+fn save_accounts(accounts: &Vec<Account>) -> Result<(), FinnError> {
+    let bin_dir = home_accounts_path()?;
+
+    let serialized = serde_json::to_string(&accounts)?;
+    let mut file = File::create(bin_dir)?;
+    file.write_all(serialized.as_bytes())?;
+
+    // Archiving is a best-effort side channel: accounts.json above is the
+    // durable write, so a failure to archive shouldn't flip the outcome of
+    // an already-successful save and risk a caller retrying (and
+    // double-applying) a mutation that was in fact persisted.
+    if let Err(err) = archive_snapshot(accounts) {
+        println!("warning: failed to archive snapshot: {}", err);
+    }
+    Ok(())
 }
 
-fn load_accounts() -> Vec<Account> {
-    let mut bin_dir = PathBuf::from(env::var("HOME").unwrap());
-    bin_dir.push("bin");
-    bin_dir.push("accounts.json");
+fn load_accounts() -> Result<Vec<Account>, FinnError> {
+    let bin_dir = home_accounts_path()?;
 
-    let file = File::open(bin_dir);
-    match file {
+    match File::open(bin_dir) {
         Ok(mut file) => {
             let mut contents = String::new();
-            file.read_to_string(&mut contents)
-                .expect("Failed to read file.");
-            serde_json::from_str(&contents).expect("Failed to deserialize accounts.")
+            file.read_to_string(&mut contents)?;
+            Ok(serde_json::from_str(&contents)?)
+        }
+        Err(_) => Ok(Vec::new()),
+    }
+}
+
+fn snapshot_dir() -> Result<PathBuf, FinnError> {
+    let home = env::var("HOME").map_err(|_| {
+        FinnError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "$HOME is not set",
+        ))
+    })?;
+    let mut dir = PathBuf::from(home);
+    dir.push("bin");
+    dir.push("finn-snapshots");
+    Ok(dir)
+}
+
+fn snapshot_limit() -> usize {
+    env::var("FINN_SNAPSHOT_LIMIT")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(20)
+}
+
+fn archive_snapshot(accounts: &Vec<Account>) -> Result<(), FinnError> {
+    let dir = snapshot_dir()?;
+    std::fs::create_dir_all(&dir)?;
+
+    let mut path = dir.clone();
+    path.push(format!("accounts-{}.json", Utc::now().to_rfc3339()));
+    let serialized = serde_json::to_string(accounts)?;
+    std::fs::write(&path, serialized)?;
+
+    prune_snapshots(&dir)
+}
+
+fn prune_snapshots(dir: &PathBuf) -> Result<(), FinnError> {
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+        .collect();
+    entries.sort();
+
+    let limit = snapshot_limit();
+    if entries.len() > limit {
+        for stale in &entries[..entries.len() - limit] {
+            std::fs::remove_file(stale)?;
         }
+    }
+    Ok(())
+}
+
+fn list_snapshots() -> Result<(), FinnError> {
+    let dir = snapshot_dir()?;
+    let mut timestamps: Vec<String> = match std::fs::read_dir(&dir) {
+        Ok(read_dir) => read_dir
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter_map(|name| {
+                name.strip_prefix("accounts-")
+                    .and_then(|rest| rest.strip_suffix(".json"))
+                    .map(|timestamp| timestamp.to_string())
+            })
+            .collect(),
         Err(_) => Vec::new(),
+    };
+    timestamps.sort();
+    timestamps.reverse();
+
+    if timestamps.is_empty() {
+        println!("No snapshots found.");
+    } else {
+        for timestamp in timestamps {
+            println!("{}", timestamp);
+        }
     }
+    Ok(())
+}
+
+fn restore_snapshot(accounts: &Vec<Account>, timestamp: String) -> Result<Vec<Account>, FinnError> {
+    let mut archive_path = snapshot_dir()?;
+    archive_path.push(format!("accounts-{}.json", timestamp));
+
+    let contents = std::fs::read_to_string(&archive_path)?;
+    let restored: Vec<Account> = serde_json::from_str(&contents)?;
+
+    // Back up the current live state before promoting the archive.
+    archive_snapshot(accounts)?;
+
+    Ok(restored)
+}
+
+fn next_tx_id(accounts: &[Account]) -> u32 {
+    accounts
+        .iter()
+        .flat_map(|a| &a.transactions)
+        .map(|t| t.tx)
+        .max()
+        .map_or(1, |max| max + 1)
 }
 
 fn display_accounts(accounts: &Vec<Account>) {
@@ -196,22 +897,27 @@ fn display_accounts(accounts: &Vec<Account>) {
         println!("No accounts found.");
     } else {
         let mut sorted_accounts = accounts.clone();
-        sorted_accounts.sort_by(|a, b| b.balance.partial_cmp(&a.balance).unwrap());
+        sorted_accounts.sort_by(|a, b| b.total.cmp(&a.total));
 
         for account in sorted_accounts {
-            println!("${:.2} {}", account.balance, account.name);
+            println!(
+                "{} available: ${}, held: ${}, total: ${}{}",
+                account.name,
+                format_money(account.available),
+                format_money(account.held),
+                format_money(account.total),
+                if account.locked { " [LOCKED]" } else { "" },
+            );
         }
 
         println!(
-            "${:.2} Total",
-            accounts
-                .iter()
-                .fold(0.0, |acc, account| acc + account.balance)
+            "${} Total",
+            format_money(accounts.iter().fold(0, |acc, account| acc + account.total))
         )
     }
 }
 
-fn main() {
+fn main() -> Result<(), FinnError> {
     let matches = command!()
         .name("Finn - Personal Finance")
         .version("1.0")
@@ -224,7 +930,7 @@ fn main() {
                 .arg(
                     arg!(<BALANCE> "initial balance")
                         .required(true)
-                        .value_parser(value_parser!(f64)),
+                        .value_parser(parse_money_arg),
                 )
                 .arg(arg!(<DESCRIPTION> "description").required(true)),
         )
@@ -235,7 +941,7 @@ fn main() {
                 .arg(
                     arg!(<AMOUNT> "deposit ammount")
                         .required(true)
-                        .value_parser(value_parser!(f64)),
+                        .value_parser(parse_money_arg),
                 )
                 .arg(arg!(<DESCRIPTION> "transaction description").required(true)),
         )
@@ -246,7 +952,7 @@ fn main() {
                 .arg(
                     arg!(<AMOUNT> "Withdrawal amount")
                         .required(true)
-                        .value_parser(value_parser!(f64)),
+                        .value_parser(parse_money_arg),
                 )
                 .arg(arg!(<DESCRIPTION> "Transaction description").required(true)),
         )
@@ -258,7 +964,7 @@ fn main() {
                 .arg(
                     arg!(<AMOUNT> "Transfer amount")
                         .required(true)
-                        .value_parser(value_parser!(f64)),
+                        .value_parser(parse_money_arg),
                 ),
         )
         .subcommand(
@@ -266,37 +972,104 @@ fn main() {
                 .about("Display transaction history for an account")
                 .arg(arg!(<NAME> "Account name").required(true)),
         )
+        .subcommand(
+            Command::new("import")
+                .about("Import a CSV of type,client,tx,amount rows")
+                .arg(arg!(<FILE> "CSV file to import").required(true)),
+        )
+        .subcommand(
+            Command::new("batch")
+                .about("Apply a JSON file of operations atomically, all or nothing")
+                .arg(arg!(<FILE> "JSON file of batch operations").required(true)),
+        )
+        .subcommand(
+            Command::new("snapshot")
+                .about("Manage point-in-time account snapshots")
+                .subcommand(Command::new("list").about("List available snapshots"))
+                .subcommand(
+                    Command::new("restore")
+                        .about("Restore accounts.json from a snapshot")
+                        .arg(arg!(<TIMESTAMP> "Snapshot RFC3339 timestamp").required(true)),
+                ),
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Serve the loaded accounts over a small JSON HTTP API")
+                .arg(
+                    arg!(<PORT> "Port to listen on")
+                        .required(true)
+                        .value_parser(value_parser!(u16)),
+                )
+                .arg(arg!(--"bind-all" "Bind to 0.0.0.0 instead of 127.0.0.1 only (or set FINN_BIND_ALL=1)")),
+        )
+        .subcommand(
+            Command::new("dispute")
+                .about("Dispute a transaction, holding its funds")
+                .arg(arg!(<NAME> "Account name").required(true))
+                .arg(
+                    arg!(<TX> "Transaction id")
+                        .required(true)
+                        .value_parser(value_parser!(u32)),
+                ),
+        )
+        .subcommand(
+            Command::new("resolve")
+                .about("Resolve a dispute, releasing its held funds")
+                .arg(arg!(<NAME> "Account name").required(true))
+                .arg(
+                    arg!(<TX> "Transaction id")
+                        .required(true)
+                        .value_parser(value_parser!(u32)),
+                ),
+        )
+        .subcommand(
+            Command::new("chargeback")
+                .about("Chargeback a disputed transaction and lock the account")
+                .arg(arg!(<NAME> "Account name").required(true))
+                .arg(
+                    arg!(<TX> "Transaction id")
+                        .required(true)
+                        .value_parser(value_parser!(u32)),
+                ),
+        )
         .get_matches();
 
-    let mut accounts: Vec<Account> = load_accounts();
+    let mut accounts: Vec<Account> = load_accounts()?;
+    let mut next_tx = next_tx_id(&accounts);
+
+    // Read-only subcommands don't touch `accounts`, so they skip the save/
+    // archive below — archiving on every invocation would otherwise push
+    // genuinely distinct pre-mutation snapshots out of the retention window
+    // with duplicate no-op snapshots.
+    let mut mutated = true;
 
     match matches.subcommand() {
         Some(("add", sub_matches)) => {
             let name = sub_matches.get_one::<String>("NAME").unwrap().clone();
-            let balance = *sub_matches.get_one::<f64>("BALANCE").unwrap();
+            let balance = *sub_matches.get_one::<i64>("BALANCE").unwrap();
             let description = sub_matches
                 .get_one::<String>("DESCRIPTION")
                 .unwrap()
                 .clone();
-            add_account(&mut accounts, name, balance, description);
+            add_account(&mut accounts, name, balance, description, &mut next_tx)?;
         }
         Some(("deposit", sub_matches)) => {
             let name = sub_matches.get_one::<String>("NAME").unwrap().clone();
-            let amount = *sub_matches.get_one::<f64>("AMOUNT").unwrap();
+            let amount = *sub_matches.get_one::<i64>("AMOUNT").unwrap();
             let description = sub_matches
                 .get_one::<String>("DESCRIPTION")
                 .unwrap()
                 .clone();
-            deposit_funds(&mut accounts, name, amount, description);
+            deposit_funds(&mut accounts, name, amount, description, &mut next_tx)?;
         }
         Some(("withdraw", sub_matches)) => {
             let name = sub_matches.get_one::<String>("NAME").unwrap().clone();
-            let amount = *sub_matches.get_one::<f64>("AMOUNT").unwrap();
+            let amount = *sub_matches.get_one::<i64>("AMOUNT").unwrap();
             let description = sub_matches
                 .get_one::<String>("DESCRIPTION")
                 .unwrap()
                 .clone();
-            withdraw_funds(&mut accounts, name, amount, description);
+            withdraw_funds(&mut accounts, name, amount, description, &mut next_tx)?;
         }
         Some(("transfer", sub_matches)) => {
             let source_name = sub_matches.get_one::<String>("SOURCE").unwrap().clone();
@@ -304,18 +1077,153 @@ fn main() {
                 .get_one::<String>("DESTINATION")
                 .unwrap()
                 .clone();
-            let amount = *sub_matches.get_one::<f64>("AMOUNT").unwrap();
-            transfer_funds(&mut accounts, source_name, dest_name, amount);
+            let amount = *sub_matches.get_one::<i64>("AMOUNT").unwrap();
+            transfer_funds(&mut accounts, source_name, dest_name, amount, &mut next_tx)?;
         }
         Some(("history", sub_matches)) => {
             let name = sub_matches.get_one::<String>("NAME").unwrap().clone();
             display_transaction_history(&accounts, name);
+            mutated = false;
+        }
+        Some(("import", sub_matches)) => {
+            let file = sub_matches.get_one::<String>("FILE").unwrap().clone();
+            import_transactions(&mut accounts, file, &mut next_tx)?;
+        }
+        Some(("batch", sub_matches)) => {
+            let file = sub_matches.get_one::<String>("FILE").unwrap().clone();
+            accounts = run_batch(&accounts, file, &mut next_tx)?;
+        }
+        Some(("serve", sub_matches)) => {
+            let port = *sub_matches.get_one::<u16>("PORT").unwrap();
+            let bind_all = sub_matches.get_flag("bind-all") || env::var("FINN_BIND_ALL").is_ok();
+            let address = if bind_all { "0.0.0.0" } else { "127.0.0.1" };
+            return serve(accounts, next_tx, port, address);
+        }
+        Some(("snapshot", sub_matches)) => match sub_matches.subcommand() {
+            Some(("list", _)) => {
+                list_snapshots()?;
+                mutated = false;
+            }
+            Some(("restore", restore_matches)) => {
+                let timestamp = restore_matches
+                    .get_one::<String>("TIMESTAMP")
+                    .unwrap()
+                    .clone();
+                accounts = restore_snapshot(&accounts, timestamp)?;
+            }
+            _ => unreachable!(),
+        },
+        Some(("dispute", sub_matches)) => {
+            let name = sub_matches.get_one::<String>("NAME").unwrap().clone();
+            let tx = *sub_matches.get_one::<u32>("TX").unwrap();
+            dispute_transaction(&mut accounts, name, tx)?;
+        }
+        Some(("resolve", sub_matches)) => {
+            let name = sub_matches.get_one::<String>("NAME").unwrap().clone();
+            let tx = *sub_matches.get_one::<u32>("TX").unwrap();
+            resolve_transaction(&mut accounts, name, tx)?;
+        }
+        Some(("chargeback", sub_matches)) => {
+            let name = sub_matches.get_one::<String>("NAME").unwrap().clone();
+            let tx = *sub_matches.get_one::<u32>("TX").unwrap();
+            chargeback_transaction(&mut accounts, name, tx)?;
         }
         None => {
             display_accounts(&accounts);
+            mutated = false;
         }
         _ => unreachable!(),
     }
 
-    save_accounts(&accounts);
+    if mutated {
+        save_accounts(&accounts)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deposit(account: &mut Account, tx: u32, amount: i64) {
+        account
+            .deposit(Transaction {
+                tx,
+                amount,
+                description: "test deposit".to_string(),
+                date: "2026-01-01".to_string(),
+                transaction_type: TransactionType::Deposit,
+                disputed: false,
+            })
+            .unwrap();
+    }
+
+    fn withdraw(account: &mut Account, tx: u32, amount: i64) {
+        account
+            .withdraw(Transaction {
+                tx,
+                amount,
+                description: "test withdrawal".to_string(),
+                date: "2026-01-01".to_string(),
+                transaction_type: TransactionType::Withdrawal,
+                disputed: false,
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn disputing_a_withdrawal_is_a_no_op() {
+        let mut account = Account::new("client".to_string());
+        deposit(&mut account, 1, 100 * MONEY_SCALE);
+        withdraw(&mut account, 2, 40 * MONEY_SCALE);
+
+        let available_before = account.available;
+        let held_before = account.held;
+        let total_before = account.total;
+
+        account.dispute(2).unwrap();
+
+        assert_eq!(account.available, available_before);
+        assert_eq!(account.held, held_before);
+        assert_eq!(account.total, total_before);
+        assert_eq!(account.available + account.held, account.total);
+    }
+
+    #[test]
+    fn chargeback_is_terminal_and_locks_the_account() {
+        let mut account = Account::new("client".to_string());
+        deposit(&mut account, 1, 100 * MONEY_SCALE);
+
+        account.dispute(1).unwrap();
+        account.chargeback(1).unwrap();
+        assert_eq!(account.available, 0);
+        assert_eq!(account.held, 0);
+        assert_eq!(account.total, 0);
+        assert!(account.locked);
+        assert_eq!(account.available + account.held, account.total);
+
+        // A repeat chargeback on the same tx must be a no-op, not a further
+        // decrement, and a locked account rejects the attempt outright.
+        assert!(account.chargeback(1).is_err());
+        assert_eq!(account.available, 0);
+        assert_eq!(account.held, 0);
+        assert_eq!(account.total, 0);
+    }
+
+    #[test]
+    fn resolve_after_chargeback_is_rejected() {
+        let mut account = Account::new("client".to_string());
+        deposit(&mut account, 1, 100 * MONEY_SCALE);
+
+        account.dispute(1).unwrap();
+        account.chargeback(1).unwrap();
+
+        // Once chargeback has locked the account, resolve must not be able
+        // to re-credit funds that chargeback already removed from total.
+        assert!(account.resolve(1).is_err());
+        assert_eq!(account.available, 0);
+        assert_eq!(account.held, 0);
+        assert_eq!(account.total, 0);
+        assert_eq!(account.available + account.held, account.total);
+    }
 }